@@ -0,0 +1,40 @@
+//! Loaded-on-demand OpenGL bindings, generated by `gl_generator` in `build.rs`.
+//!
+//! Wrapping the generated struct in an `Rc` makes the resulting `Gl` handle
+//! cheap to clone and thread through the render helpers explicitly, instead
+//! of relying on the global function-pointer table the `gl` crate exposes.
+
+use std::ops::Deref;
+use std::os::raw::c_void;
+use std::rc::Rc;
+
+#[allow(clippy::all, dead_code)]
+mod bindings {
+    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+}
+
+pub use bindings::types;
+pub use bindings::*;
+
+/// A cloneable handle to a loaded set of GL function pointers.
+#[derive(Clone)]
+pub struct Gl(Rc<bindings::Gl>);
+
+impl Gl {
+    /// Loads every GL function pointer through `loader` (e.g.
+    /// `window.get_proc_address`). Must be called with a current GL context.
+    pub fn load_with<F>(loader: F) -> Gl
+    where
+        F: FnMut(&'static str) -> *const c_void,
+    {
+        Gl(Rc::new(bindings::Gl::load_with(loader)))
+    }
+}
+
+impl Deref for Gl {
+    type Target = bindings::Gl;
+
+    fn deref(&self) -> &bindings::Gl {
+        &self.0
+    }
+}
@@ -1,247 +1,182 @@
-use gl::types::{GLchar, GLfloat, GLint, GLsizei, GLsizeiptr, GLuint};
-use glfw::{Action, Context, Key};
+use crate::window::create_window;
+use glam::{Mat4, Vec3};
+use render::{Buffer, ShaderProgram, ShaderWatcher, Texture, VertexArray};
 use std::f32::consts::FRAC_PI_2;
-use std::ffi::CString;
-use std::io::Write;
-use std::os::raw::c_void;
-use std::sync::mpsc::Receiver;
-use std::{io, mem, ptr};
+use std::time::Instant;
+use winit::event::{ElementState, Event, KeyEvent, WindowEvent};
+use winit::event_loop::EventLoop;
+use winit::keyboard::{KeyCode, PhysicalKey};
 
-const WINDOW_RESOLUTION: (u32, u32) = (800, 600);
+mod gl;
+mod render;
+mod window;
 
-const VERTEX_SHADER_SOURCE: &str = include_str!("../shader.vert");
-const FRAGMENT_SHADER_SOURCE: &str = include_str!("../shader.frag");
+const VERTEX_SHADER_PATH: &str = "shader.vert";
+const FRAGMENT_SHADER_PATH: &str = "shader.frag";
+const TEXTURE_PATH: &str = "texture.png";
 
 fn main() {
-    // Create a GLFW window and hook to OpenGL function pointers
-    let (mut glfw, mut window, events) = init_and_create_glfw_window();
+    let event_loop = EventLoop::new().expect("Failed to create the event loop");
+    let gl_window = create_window(&event_loop);
+    let gl = gl_window.gl.clone();
 
     // Compile shaders
-    let shader_program = unsafe { compiler_shader() };
+    let mut shader_program = compile_shader_program(&gl);
+    // Watch the shader sources on disk so edits can be picked up live
+    let mut shader_watcher = ShaderWatcher::new(&gl, VERTEX_SHADER_PATH, FRAGMENT_SHADER_PATH);
     // Populate a VAO with a triangle
-    let vao = unsafe { setup_vertex_data() };
-
-    let mut last_frame = glfw.get_time();
-
-    // Render loop
-    while !window.should_close() {
-        // Handle events
-        process_events(&mut window, &events);
-
-        // Render
-        unsafe {
-            gl::ClearColor(0.2, 0.3, 0.3, 1.0);
-            gl::Clear(gl::COLOR_BUFFER_BIT);
-
-            // Configure shader
-            let time = glfw.get_time() as f32 * 5.0;
-            let red_value = (time.cos() / 2.0) + 0.5;
-            let green_value = (time.sin() / 2.0) + 0.5;
-            let blue_value = (time.cos() / 2.0 + FRAC_PI_2) + 0.5;
-            let vertex_color_location =
-                gl::GetUniformLocation(shader_program, CString::new("albedo").unwrap().as_ptr());
-
-            // Enable shader
-            gl::UseProgram(shader_program);
-            // Send data to shader
-            gl::Uniform4f(
-                vertex_color_location,
-                red_value,
-                green_value,
-                blue_value,
-                1.0,
-            );
-
-            gl::BindVertexArray(vao); // Not needed 'cause its the only VAO but that's how its supposed to work
-            gl::DrawArrays(gl::TRIANGLES, 0, 3);
-            // gl::BindVertexArray(0); // No need to unbind every time
-        }
-
-        // GLFW stuff
-        window.swap_buffers();
-        glfw.poll_events();
-
-        print!("\rFPS: {}", 1.0 / (glfw.get_time() - last_frame));
-        last_frame = glfw.get_time();
-        io::stdout().flush().unwrap();
-    }
-}
-
-fn init_and_create_glfw_window() -> (glfw::Glfw, glfw::Window, Receiver<(f64, glfw::WindowEvent)>) {
-    // Initialize GLFW
-    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
-
-    // Configure OpenGL version to use
-    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
-    glfw.window_hint(glfw::WindowHint::OpenGlProfile(
-        glfw::OpenGlProfileHint::Core,
-    ));
-
-    // Force i3 to show it as a floating window
-    glfw.window_hint(glfw::WindowHint::Resizable(false));
-
-    #[cfg(target_os = "macos")]
-        glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
-
-    // Create the window
-    let (mut window, events) = glfw
-        .create_window(
-            WINDOW_RESOLUTION.0,
-            WINDOW_RESOLUTION.1,
-            "OpenGL playground",
-            glfw::WindowMode::Windowed,
-        )
-        .expect("Failed to create GLFW window !");
-
-    window.make_current();
-    window.set_key_polling(true);
-    window.set_framebuffer_size_polling(true);
-
-    // Cap the window at 60 FPS
-    glfw.set_swap_interval(glfw::SwapInterval::Sync(1));
-
-    // Initialize OpenGL functions
-    gl::load_with(|s| window.get_proc_address(s) as *const _);
-
-    (glfw, window, events)
-}
-
-fn process_events(window: &mut glfw::Window, events: &Receiver<(f64, glfw::WindowEvent)>) {
-    for (_, event) in glfw::flush_messages(events) {
-        match event {
-            glfw::WindowEvent::FramebufferSize(width, height) => unsafe {
-                gl::Viewport(0, 0, width, height)
+    let (vao, _vbo) = setup_vertex_data(&gl);
+    // Load the texture sampled by the fragment shader
+    let texture = Texture::load(&gl, TEXTURE_PATH)
+        .unwrap_or_else(|err| panic!("Failed to load {}: {}", TEXTURE_PATH, err));
+
+    let start_time = Instant::now();
+    let mut last_frame = start_time.elapsed().as_secs_f32();
+    let mut elapsed = 0.0_f32;
+
+    event_loop
+        .run(move |event, elwt| match event {
+            Event::WindowEvent { event, .. } => match event {
+                WindowEvent::Resized(size) => gl_window.resize(size),
+                WindowEvent::CloseRequested => elwt.exit(),
+                WindowEvent::KeyboardInput {
+                    event:
+                        KeyEvent {
+                            physical_key: PhysicalKey::Code(KeyCode::Escape),
+                            state: ElementState::Pressed,
+                            ..
+                        },
+                    ..
+                } => elwt.exit(),
+                WindowEvent::RedrawRequested => {
+                    // Pick up shader edits made on disk, if any
+                    if let Some(reloaded) = shader_watcher.poll() {
+                        shader_program = reloaded;
+                    }
+
+                    let now = start_time.elapsed().as_secs_f32();
+                    let delta_time = now - last_frame;
+                    last_frame = now;
+                    elapsed += delta_time;
+
+                    // Render
+                    unsafe {
+                        gl.ClearColor(0.2, 0.3, 0.3, 1.0);
+                        gl.Clear(gl::COLOR_BUFFER_BIT);
+                    }
+
+                    // Configure shader
+                    let time = elapsed * 5.0;
+                    let red_value = (time.cos() / 2.0) + 0.5;
+                    let green_value = (time.sin() / 2.0) + 0.5;
+                    let blue_value = (time.cos() / 2.0 + FRAC_PI_2) + 0.5;
+                    let vertex_color_location = shader_program.uniform_location("albedo");
+                    let texture_location = shader_program.uniform_location("tex");
+                    let transform_location = shader_program.uniform_location("transform");
+                    let color_flow_location = shader_program.uniform_location("colorFlow");
+
+                    // Translate the triangle back and forth in screen space
+                    let x_offset = map(elapsed.sin(), -1.0, 1.0, -0.5, 0.5);
+                    let transform = Mat4::from_translation(Vec3::new(x_offset, 0.0, 0.0));
+                    // Slowly rotate the per-vertex colors through the color wheel
+                    let color_flow = Mat4::from_rotation_z(elapsed);
+
+                    // Enable shader
+                    shader_program.use_program();
+                    // Send data to shader
+                    unsafe {
+                        gl.Uniform4f(
+                            vertex_color_location,
+                            red_value,
+                            green_value,
+                            blue_value,
+                            1.0,
+                        );
+                        gl.Uniform1i(texture_location, 0);
+                        gl.UniformMatrix4fv(
+                            transform_location,
+                            1,
+                            gl::FALSE,
+                            transform.to_cols_array().as_ptr(),
+                        );
+                        gl.UniformMatrix4fv(
+                            color_flow_location,
+                            1,
+                            gl::FALSE,
+                            color_flow.to_cols_array().as_ptr(),
+                        );
+                    }
+                    texture.bind(gl::TEXTURE0);
+
+                    vao.bind(); // Not needed 'cause its the only VAO but that's how its supposed to work
+                    unsafe {
+                        gl.DrawArrays(gl::TRIANGLES, 0, 3);
+                    }
+                    // VertexArray::unbind(&gl); // No need to unbind every time
+
+                    gl_window.swap_buffers();
+
+                    print!("\rFPS: {}", 1.0 / delta_time);
+                }
+                _ => {}
             },
-            glfw::WindowEvent::Key(Key::Escape, _, Action::Press, _) => {
-                window.set_should_close(true);
-            }
+            Event::AboutToWait => gl_window.window.request_redraw(),
             _ => {}
-        }
-    }
+        })
+        .expect("Event loop exited with an error");
 }
 
-unsafe fn compiler_shader() -> GLuint {
-    // Build vertex shader
-    let vertex_shader = gl::CreateShader(gl::VERTEX_SHADER);
-    let c_str_vert = CString::new(VERTEX_SHADER_SOURCE.as_bytes()).unwrap();
-    gl::ShaderSource(vertex_shader, 1, &c_str_vert.as_ptr(), ptr::null());
-    compile_shader_with_debug(vertex_shader, "VERTEX");
-
-    // Build fragment shader
-    let frag_shader = gl::CreateShader(gl::FRAGMENT_SHADER);
-    let c_str_frag = CString::new(FRAGMENT_SHADER_SOURCE.as_bytes()).unwrap();
-    gl::ShaderSource(frag_shader, 1, &c_str_frag.as_ptr(), ptr::null());
-    compile_shader_with_debug(frag_shader, "FRAGMENT");
-
-    // Link shaders
-    let shader_program = gl::CreateProgram();
-    gl::AttachShader(shader_program, vertex_shader);
-    gl::AttachShader(shader_program, frag_shader);
-    gl::LinkProgram(shader_program);
-
-    // Check for linking errors
-    check_linking_errors(shader_program);
-
-    // Cleanup
-    gl::DeleteShader(vertex_shader);
-    gl::DeleteShader(frag_shader);
-
-    shader_program
+/// Remaps `x` from the `[in_min, in_max]` range to the `[out_min, out_max]` range.
+fn map(x: f32, in_min: f32, in_max: f32, out_min: f32, out_max: f32) -> f32 {
+    (x - in_min) * (out_max - out_min) / (in_max - in_min) + out_min
 }
 
-unsafe fn compile_shader_with_debug(shader_id: GLuint, message: &str) {
-    // Compile
-    gl::CompileShader(shader_id);
-
-    // Check for compilation errors
-    let mut success = gl::FALSE as GLint;
-    let mut info_log = Vec::with_capacity(512);
-    info_log.set_len(512 - 1); // Skip the trailing null character
-
-    gl::GetShaderiv(shader_id, gl::COMPILE_STATUS, &mut success);
-    if success != gl::TRUE as GLint {
-        gl::GetShaderInfoLog(
-            shader_id,
-            512,
-            ptr::null_mut(),
-            info_log.as_mut_ptr() as *mut GLchar,
-        );
-        panic!(
-            "ERROR::SHADER::{}::COMPILATION_FAILED\n{}",
-            message,
-            std::str::from_utf8(&info_log).unwrap()
-        );
-    }
+/// Reads and links the vertex/fragment shaders from disk, panicking with the
+/// GL info log if either step fails. Only used for the initial load; later
+/// reloads go through `ShaderWatcher`, which tolerates failures.
+fn compile_shader_program(gl: &gl::Gl) -> ShaderProgram {
+    let vertex_src = std::fs::read_to_string(VERTEX_SHADER_PATH)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", VERTEX_SHADER_PATH, err));
+    let fragment_src = std::fs::read_to_string(FRAGMENT_SHADER_PATH)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", FRAGMENT_SHADER_PATH, err));
+
+    ShaderProgram::from_sources(gl, &vertex_src, &fragment_src)
+        .unwrap_or_else(|log| panic!("ERROR::SHADER::PROGRAM::COMPILATION_FAILED\n{}", log))
 }
 
-unsafe fn check_linking_errors(shader_program: GLuint) {
-    let mut success = gl::FALSE as GLint;
-    let mut info_log = Vec::with_capacity(512);
-    info_log.set_len(512 - 1); // Skip the trailing null character
-
-    gl::GetProgramiv(shader_program, gl::LINK_STATUS, &mut success);
-    if success != gl::TRUE as GLint {
-        gl::GetProgramInfoLog(
-            shader_program,
-            512,
-            ptr::null_mut(),
-            info_log.as_mut_ptr() as *mut GLchar,
-        );
-        panic!(
-            "ERROR::SHADER::PROGRAM::COMPILATION_FAILED\n{}",
-            std::str::from_utf8(&info_log).unwrap()
-        );
-    }
-}
-
-unsafe fn setup_vertex_data() -> GLuint {
-    let vertices: [f32; 9] = [
-        -0.5, -0.5, 0.0, // bottom left
-        0.5, -0.5, 0.0, // bottom right
-        0.0, 0.5, 0.0, // up
+fn setup_vertex_data(gl: &gl::Gl) -> (VertexArray, Buffer) {
+    // Interleaved `position: vec3, uv: vec2, color: vec3` per vertex
+    let vertices: [f32; 24] = [
+        -0.5, -0.5, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, // bottom left: red
+        0.5, -0.5, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, // bottom right: green
+        0.0, 0.5, 0.0, 0.5, 1.0, 0.0, 0.0, 1.0, // up: blue
     ];
 
     // Create a VAO and its VBO
     // VBO: Vertex Buffer Objects
     // VAO: Vertex Array Object
-    let (vbo, vao) = {
-        let (mut vbo, mut vao) = (0, 0);
-        gl::GenVertexArrays(1, &mut vao);
-        gl::GenBuffers(1, &mut vbo);
-
-        (vbo, vao)
-    };
+    let vao = VertexArray::new(gl);
+    let vbo = Buffer::new(gl, gl::ARRAY_BUFFER);
 
     // Bind the VAO first, then bind the VBO and configure them
-    gl::BindVertexArray(vao);
-    gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+    vao.bind();
     // Send vertices to the GPU
-    gl::BufferData(
-        gl::ARRAY_BUFFER,
-        (vertices.len() * mem::size_of_val(&vertices[0])) as GLsizeiptr,
-        &vertices[0] as *const f32 as *const c_void,
-        gl::STATIC_DRAW,
-    );
-
-    // Describe an attribute of the vertex array
-    gl::VertexAttribPointer(
-        0, // Attribute 0
-        3, // with 3 values
-        gl::FLOAT, // of type float
-        gl::FALSE, // not normalized
-        3 * mem::size_of::<GLfloat>() as GLsizei, // stride: how many byte between vertices
-        ptr::null(), // offset to start at (in bytes)
-    );
-    // Enable attribute 0, will be in the location instruction of the vertex shader
-    gl::EnableVertexAttribArray(0);
+    vbo.data(&vertices, gl::STATIC_DRAW);
+
+    // Describe the attributes of the vertex array
+    // Attribute 0: position, 3 values of type float, stride of 8 floats, no offset
+    vbo.vertex_attrib(0, 3, 8, 0);
+    // Attribute 1: uv, 2 values of type float, stride of 8 floats, offset of 3 floats
+    vbo.vertex_attrib(1, 2, 8, 3);
+    // Attribute 2: color, 3 values of type float, stride of 8 floats, offset of 5 floats
+    vbo.vertex_attrib(2, 3, 8, 5);
 
     // VBO is associated with the VAO, we can safely unbind it
-    gl::BindBuffer(gl::ARRAY_BUFFER, 0);
-    // Unbind VAO to avoid accidental modification of it even though its kinda hard to mess it up
-    gl::BindVertexArray(0);
+    VertexArray::unbind(gl);
 
-    // gl::PolygonMode(gl::FRONT_AND_BACK, gl::LINE);
+    // gl.PolygonMode(gl::FRONT_AND_BACK, gl::LINE);
 
-    // The VBO is bound to the VAO so we only need to care of the VAO
-    vao
+    // The VBO is bound to the VAO so we only need to care of the VAO, but we
+    // keep it alive alongside it so it isn't dropped (and deleted) early
+    (vao, vbo)
 }
@@ -0,0 +1,73 @@
+use crate::gl;
+use crate::gl::types::{GLenum, GLint, GLsizei, GLsizeiptr, GLuint};
+use crate::gl::Gl;
+use std::mem;
+use std::os::raw::c_void;
+
+/// A GL buffer object (VBO, EBO, ...) bound to a fixed `target`.
+pub struct Buffer {
+    gl: Gl,
+    id: GLuint,
+    target: GLenum,
+}
+
+impl Buffer {
+    pub fn new(gl: &Gl, target: GLenum) -> Buffer {
+        let mut id = 0;
+        unsafe {
+            gl.GenBuffers(1, &mut id);
+        }
+        Buffer {
+            gl: gl.clone(),
+            id,
+            target,
+        }
+    }
+
+    pub fn bind(&self) {
+        unsafe {
+            self.gl.BindBuffer(self.target, self.id);
+        }
+    }
+
+    /// Uploads `data` to the buffer, binding it first.
+    pub fn data<T>(&self, data: &[T], usage: GLenum) {
+        self.bind();
+        unsafe {
+            self.gl.BufferData(
+                self.target,
+                mem::size_of_val(data) as GLsizeiptr,
+                data.as_ptr() as *const c_void,
+                usage,
+            );
+        }
+    }
+
+    /// Binds the buffer and describes one of its vertex attributes.
+    ///
+    /// `stride` and `offset` are expressed in elements, matching the layout
+    /// of the type the buffer was filled with (e.g. `3` for an
+    /// interleaved `[x, y, z]` vertex).
+    pub fn vertex_attrib(&self, index: GLuint, size: GLint, stride: usize, offset: usize) {
+        self.bind();
+        unsafe {
+            self.gl.VertexAttribPointer(
+                index,
+                size,
+                gl::FLOAT,
+                gl::FALSE,
+                (stride * mem::size_of::<f32>()) as GLsizei,
+                (offset * mem::size_of::<f32>()) as *const c_void,
+            );
+            self.gl.EnableVertexAttribArray(index);
+        }
+    }
+}
+
+impl Drop for Buffer {
+    fn drop(&mut self) {
+        unsafe {
+            self.gl.DeleteBuffers(1, &self.id);
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! Safe RAII wrappers around the raw GL objects `main` used to manage by hand.
+//!
+//! Every type here owns a single GL handle and deletes it in `Drop`, so the
+//! call sites no longer need to remember to pair `gl::Gen*`/`gl::Create*`
+//! with a matching `gl::Delete*`.
+
+mod buffer;
+mod program;
+mod shader;
+mod texture;
+mod vertex_array;
+mod watcher;
+
+pub use buffer::Buffer;
+pub use program::ShaderProgram;
+pub use shader::Shader;
+pub use texture::Texture;
+pub use vertex_array::VertexArray;
+pub use watcher::ShaderWatcher;
@@ -0,0 +1,90 @@
+use crate::gl;
+use crate::gl::types::{GLchar, GLint, GLuint};
+use crate::gl::Gl;
+use crate::render::Shader;
+use std::ffi::CString;
+use std::ptr;
+
+/// A linked GL shader program.
+pub struct ShaderProgram {
+    gl: Gl,
+    id: GLuint,
+}
+
+impl ShaderProgram {
+    /// Compiles a vertex/fragment shader pair from source and links them.
+    ///
+    /// Convenience wrapper around [`Shader::from_source`] and [`ShaderProgram::link`]
+    /// for the common vertex+fragment case.
+    pub fn from_sources(
+        gl: &Gl,
+        vertex_src: &str,
+        fragment_src: &str,
+    ) -> Result<ShaderProgram, String> {
+        let vertex_shader = Shader::from_source(gl, gl::VERTEX_SHADER, vertex_src)?;
+        let fragment_shader = Shader::from_source(gl, gl::FRAGMENT_SHADER, fragment_src)?;
+
+        ShaderProgram::link(gl, &[vertex_shader, fragment_shader])
+    }
+
+    /// Attaches `shaders` and links them into a program.
+    ///
+    /// Returns the info log as the `Err` instead of panicking.
+    pub fn link(gl: &Gl, shaders: &[Shader]) -> Result<ShaderProgram, String> {
+        unsafe {
+            let id = gl.CreateProgram();
+            for shader in shaders {
+                gl.AttachShader(id, shader.id());
+            }
+            gl.LinkProgram(id);
+
+            let mut success = gl::FALSE as GLint;
+            gl.GetProgramiv(id, gl::LINK_STATUS, &mut success);
+            if success != gl::TRUE as GLint {
+                let log = program_info_log(gl, id);
+                gl.DeleteProgram(id);
+                return Err(log);
+            }
+
+            Ok(ShaderProgram {
+                gl: gl.clone(),
+                id,
+            })
+        }
+    }
+
+    pub fn use_program(&self) {
+        unsafe {
+            self.gl.UseProgram(self.id);
+        }
+    }
+
+    pub fn uniform_location(&self, name: &str) -> GLint {
+        let c_name = CString::new(name).unwrap();
+        unsafe { self.gl.GetUniformLocation(self.id, c_name.as_ptr()) }
+    }
+}
+
+impl Drop for ShaderProgram {
+    fn drop(&mut self) {
+        unsafe {
+            self.gl.DeleteProgram(self.id);
+        }
+    }
+}
+
+unsafe fn program_info_log(gl: &Gl, program_id: GLuint) -> String {
+    let mut len = 0;
+    gl.GetProgramiv(program_id, gl::INFO_LOG_LENGTH, &mut len);
+
+    let mut info_log = vec![0u8; len.max(0) as usize];
+    gl.GetProgramInfoLog(
+        program_id,
+        len,
+        ptr::null_mut(),
+        info_log.as_mut_ptr() as *mut GLchar,
+    );
+    info_log.truncate(info_log.iter().position(|&b| b == 0).unwrap_or(info_log.len()));
+
+    String::from_utf8_lossy(&info_log).into_owned()
+}
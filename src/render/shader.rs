@@ -0,0 +1,68 @@
+use crate::gl;
+use crate::gl::types::{GLchar, GLenum, GLint, GLuint};
+use crate::gl::Gl;
+use std::ffi::CString;
+use std::ptr;
+
+/// A compiled (but not yet linked) GL shader stage.
+pub struct Shader {
+    gl: Gl,
+    id: GLuint,
+}
+
+impl Shader {
+    /// Compiles `src` as a shader of the given `stage` (e.g. `gl::VERTEX_SHADER`).
+    ///
+    /// Returns the info log as the `Err` instead of panicking, so callers can
+    /// decide what to do with a broken shader (report it, keep the previous
+    /// one, ...).
+    pub fn from_source(gl: &Gl, stage: GLenum, src: &str) -> Result<Shader, String> {
+        unsafe {
+            let id = gl.CreateShader(stage);
+            let c_str = CString::new(src.as_bytes()).unwrap();
+            gl.ShaderSource(id, 1, &c_str.as_ptr(), ptr::null());
+            gl.CompileShader(id);
+
+            let mut success = gl::FALSE as GLint;
+            gl.GetShaderiv(id, gl::COMPILE_STATUS, &mut success);
+            if success != gl::TRUE as GLint {
+                let log = shader_info_log(gl, id);
+                gl.DeleteShader(id);
+                return Err(log);
+            }
+
+            Ok(Shader {
+                gl: gl.clone(),
+                id,
+            })
+        }
+    }
+
+    pub(super) fn id(&self) -> GLuint {
+        self.id
+    }
+}
+
+impl Drop for Shader {
+    fn drop(&mut self) {
+        unsafe {
+            self.gl.DeleteShader(self.id);
+        }
+    }
+}
+
+unsafe fn shader_info_log(gl: &Gl, shader_id: GLuint) -> String {
+    let mut len = 0;
+    gl.GetShaderiv(shader_id, gl::INFO_LOG_LENGTH, &mut len);
+
+    let mut info_log = vec![0u8; len.max(0) as usize];
+    gl.GetShaderInfoLog(
+        shader_id,
+        len,
+        ptr::null_mut(),
+        info_log.as_mut_ptr() as *mut GLchar,
+    );
+    info_log.truncate(info_log.iter().position(|&b| b == 0).unwrap_or(info_log.len()));
+
+    String::from_utf8_lossy(&info_log).into_owned()
+}
@@ -0,0 +1,125 @@
+use crate::gl::types::{GLenum, GLint, GLsizei, GLuint};
+use crate::gl::Gl;
+use image::GenericImageView;
+use std::os::raw::c_void;
+use std::path::Path;
+
+/// A 2D GL texture, decoded from an image file on disk.
+pub struct Texture {
+    gl: Gl,
+    id: GLuint,
+}
+
+impl Texture {
+    /// Decodes `path` (PNG, JPEG and AVIF via the `image` crate, JPEG-XL via
+    /// `jxl-oxide` when the `jxl` feature is enabled) and uploads it as a
+    /// mipmapped `GL_TEXTURE_2D`.
+    pub fn load(gl: &Gl, path: impl AsRef<Path>) -> Result<Texture, String> {
+        let path = path.as_ref();
+
+        #[cfg(feature = "jxl")]
+        let img = if path.extension().and_then(|ext| ext.to_str()) == Some("jxl") {
+            load_jxl(path)?
+        } else {
+            image::open(path).map_err(|err| err.to_string())?
+        };
+        #[cfg(not(feature = "jxl"))]
+        let img = image::open(path).map_err(|err| err.to_string())?;
+
+        // Image rows are stored top-to-bottom, GL expects the first row at
+        // the bottom-left origin.
+        let img = img.flipv();
+        let (width, height) = img.dimensions();
+        let (format, data) = match img.color().channel_count() {
+            3 => (crate::gl::RGB, img.to_rgb8().into_raw()),
+            _ => (crate::gl::RGBA, img.to_rgba8().into_raw()),
+        };
+
+        let mut id = 0;
+        unsafe {
+            gl.GenTextures(1, &mut id);
+            gl.BindTexture(crate::gl::TEXTURE_2D, id);
+
+            gl.TexParameteri(
+                crate::gl::TEXTURE_2D,
+                crate::gl::TEXTURE_WRAP_S,
+                crate::gl::REPEAT as GLint,
+            );
+            gl.TexParameteri(
+                crate::gl::TEXTURE_2D,
+                crate::gl::TEXTURE_WRAP_T,
+                crate::gl::REPEAT as GLint,
+            );
+            gl.TexParameteri(
+                crate::gl::TEXTURE_2D,
+                crate::gl::TEXTURE_MIN_FILTER,
+                crate::gl::LINEAR_MIPMAP_LINEAR as GLint,
+            );
+            gl.TexParameteri(
+                crate::gl::TEXTURE_2D,
+                crate::gl::TEXTURE_MAG_FILTER,
+                crate::gl::LINEAR as GLint,
+            );
+
+            gl.TexImage2D(
+                crate::gl::TEXTURE_2D,
+                0,
+                format as GLint,
+                width as GLsizei,
+                height as GLsizei,
+                0,
+                format,
+                crate::gl::UNSIGNED_BYTE,
+                data.as_ptr() as *const c_void,
+            );
+            gl.GenerateMipmap(crate::gl::TEXTURE_2D);
+        }
+
+        Ok(Texture {
+            gl: gl.clone(),
+            id,
+        })
+    }
+
+    /// Binds the texture to the given texture unit (e.g. `gl::TEXTURE0`).
+    pub fn bind(&self, unit: GLenum) {
+        unsafe {
+            self.gl.ActiveTexture(unit);
+            self.gl.BindTexture(crate::gl::TEXTURE_2D, self.id);
+        }
+    }
+}
+
+impl Drop for Texture {
+    fn drop(&mut self) {
+        unsafe {
+            self.gl.DeleteTextures(1, &self.id);
+        }
+    }
+}
+
+#[cfg(feature = "jxl")]
+fn load_jxl(path: &Path) -> Result<image::DynamicImage, String> {
+    let data = std::fs::read(path).map_err(|err| err.to_string())?;
+    let jxl_image = jxl_oxide::JxlImage::from_reader(data.as_slice()).map_err(|err| err.to_string())?;
+    let render = jxl_image.render_frame(0).map_err(|err| err.to_string())?;
+    let buffer = render.image_all_channels();
+
+    // `FrameBuffer` samples are linear floats in `0.0..=1.0`; `image` wants bytes.
+    let data: Vec<u8> = buffer
+        .buf()
+        .iter()
+        .map(|&sample| (sample.clamp(0.0, 1.0) * 255.0).round() as u8)
+        .collect();
+    let (width, height) = (buffer.width() as u32, buffer.height() as u32);
+
+    match buffer.channels() {
+        4 => image::RgbaImage::from_raw(width, height, data)
+            .map(image::DynamicImage::ImageRgba8)
+            .ok_or_else(|| "invalid JPEG-XL frame buffer".to_string()),
+        3 => image::RgbImage::from_raw(width, height, data)
+            .map(image::DynamicImage::ImageRgb8)
+            .ok_or_else(|| "invalid JPEG-XL frame buffer".to_string()),
+        n => Err(format!("unsupported JPEG-XL channel count: {n}")),
+    }
+}
@@ -0,0 +1,38 @@
+use crate::gl::types::GLuint;
+use crate::gl::Gl;
+
+/// A GL vertex array object.
+pub struct VertexArray {
+    gl: Gl,
+    id: GLuint,
+}
+
+impl VertexArray {
+    pub fn new(gl: &Gl) -> VertexArray {
+        let mut id = 0;
+        unsafe {
+            gl.GenVertexArrays(1, &mut id);
+        }
+        VertexArray { gl: gl.clone(), id }
+    }
+
+    pub fn bind(&self) {
+        unsafe {
+            self.gl.BindVertexArray(self.id);
+        }
+    }
+
+    pub fn unbind(gl: &Gl) {
+        unsafe {
+            gl.BindVertexArray(0);
+        }
+    }
+}
+
+impl Drop for VertexArray {
+    fn drop(&mut self) {
+        unsafe {
+            self.gl.DeleteVertexArrays(1, &self.id);
+        }
+    }
+}
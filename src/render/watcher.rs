@@ -0,0 +1,72 @@
+use crate::gl::Gl;
+use crate::render::ShaderProgram;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Watches a vertex/fragment shader pair on disk and recompiles them on
+/// change, so edits can be picked up without restarting the app.
+///
+/// If a reload fails to compile, the previous program keeps running and the
+/// info log is printed instead of crashing.
+pub struct ShaderWatcher {
+    gl: Gl,
+    vertex_path: PathBuf,
+    fragment_path: PathBuf,
+    vertex_modified: SystemTime,
+    fragment_modified: SystemTime,
+}
+
+impl ShaderWatcher {
+    pub fn new(
+        gl: &Gl,
+        vertex_path: impl AsRef<Path>,
+        fragment_path: impl AsRef<Path>,
+    ) -> ShaderWatcher {
+        let vertex_path = vertex_path.as_ref().to_path_buf();
+        let fragment_path = fragment_path.as_ref().to_path_buf();
+        let vertex_modified = modified(&vertex_path);
+        let fragment_modified = modified(&fragment_path);
+
+        ShaderWatcher {
+            gl: gl.clone(),
+            vertex_path,
+            fragment_path,
+            vertex_modified,
+            fragment_modified,
+        }
+    }
+
+    /// Checks whether either shader file changed since the last call and, if
+    /// so, recompiles the program. Returns `Some` only when a new program was
+    /// successfully linked; on a compile/link failure the info log is
+    /// printed and `None` is returned so the caller keeps its current program.
+    pub fn poll(&mut self) -> Option<ShaderProgram> {
+        let vertex_modified = modified(&self.vertex_path);
+        let fragment_modified = modified(&self.fragment_path);
+
+        if vertex_modified == self.vertex_modified && fragment_modified == self.fragment_modified
+        {
+            return None;
+        }
+        self.vertex_modified = vertex_modified;
+        self.fragment_modified = fragment_modified;
+
+        let vertex_src = fs::read_to_string(&self.vertex_path).ok()?;
+        let fragment_src = fs::read_to_string(&self.fragment_path).ok()?;
+
+        match ShaderProgram::from_sources(&self.gl, &vertex_src, &fragment_src) {
+            Ok(program) => Some(program),
+            Err(log) => {
+                eprintln!("ERROR::SHADER::RELOAD_FAILED\n{}", log);
+                None
+            }
+        }
+    }
+}
+
+fn modified(path: &Path) -> SystemTime {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
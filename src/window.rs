@@ -0,0 +1,121 @@
+//! Window and GL context/surface creation via `winit` + `glutin`.
+//!
+//! Backend selection (X11/GLX vs Wayland/EGL) is controlled by this crate's
+//! `wayland` and `egl` Cargo features (see `Cargo.toml`), which forward to
+//! the matching `winit`/`glutin`/`glutin-winit` features; those crates pick
+//! the concrete display API from whatever was compiled in, so there is
+//! nothing to branch on here at the source level.
+
+use crate::gl::Gl;
+use glutin::config::ConfigTemplateBuilder;
+use glutin::context::{ContextApi, ContextAttributesBuilder, NotCurrentGlContext, Version};
+use glutin::display::GetGlDisplay;
+use glutin::prelude::*;
+use glutin::surface::{Surface, SwapInterval, WindowSurface};
+use glutin_winit::{DisplayBuilder, GlWindow as _};
+use raw_window_handle::HasRawWindowHandle;
+use std::ffi::CString;
+use std::num::NonZeroU32;
+use winit::dpi::PhysicalSize;
+use winit::event_loop::EventLoop;
+use winit::window::{Window, WindowBuilder};
+
+pub const WINDOW_RESOLUTION: (u32, u32) = (800, 600);
+
+/// The window, its GL surface/context, and the loaded GL function pointers.
+pub struct AppWindow {
+    pub window: Window,
+    pub gl_surface: Surface<WindowSurface>,
+    pub gl_context: glutin::context::PossiblyCurrentContext,
+    pub gl: Gl,
+}
+
+pub fn create_window(event_loop: &EventLoop<()>) -> AppWindow {
+    let window_builder = WindowBuilder::new()
+        .with_title("OpenGL playground")
+        .with_inner_size(PhysicalSize::new(WINDOW_RESOLUTION.0, WINDOW_RESOLUTION.1))
+        .with_resizable(false);
+
+    let template = ConfigTemplateBuilder::new();
+    let display_builder = DisplayBuilder::new().with_window_builder(Some(window_builder));
+
+    let (window, gl_config) = display_builder
+        .build(event_loop, template, |configs| {
+            // Prefer the config with the most MSAA samples
+            configs
+                .reduce(|accum, config| {
+                    if config.num_samples() > accum.num_samples() {
+                        config
+                    } else {
+                        accum
+                    }
+                })
+                .expect("No GL config available")
+        })
+        .expect("Failed to create window and GL config");
+    let window = window.expect("Failed to create window");
+
+    let raw_window_handle = window.raw_window_handle();
+    let gl_display = gl_config.display();
+
+    let context_attributes = ContextAttributesBuilder::new()
+        .with_context_api(ContextApi::OpenGl(Some(Version::new(3, 3))))
+        .build(Some(raw_window_handle));
+
+    let not_current_context = unsafe {
+        gl_display
+            .create_context(&gl_config, &context_attributes)
+            .expect("Failed to create GL context")
+    };
+
+    let surface_attributes = window.build_surface_attributes(Default::default());
+    let gl_surface = unsafe {
+        gl_display
+            .create_window_surface(&gl_config, &surface_attributes)
+            .expect("Failed to create GL surface")
+    };
+
+    let gl_context = not_current_context
+        .make_current(&gl_surface)
+        .expect("Failed to make GL context current");
+
+    // Cap the window at 60 FPS
+    gl_surface
+        .set_swap_interval(&gl_context, SwapInterval::Wait(NonZeroU32::new(1).unwrap()))
+        .expect("Failed to enable vsync");
+
+    // Load the OpenGL function pointers into an explicit context object
+    let gl = Gl::load_with(|s| {
+        let s = CString::new(s).unwrap();
+        gl_display.get_proc_address(s.as_c_str()) as *const _
+    });
+
+    AppWindow {
+        window,
+        gl_surface,
+        gl_context,
+        gl,
+    }
+}
+
+impl AppWindow {
+    pub fn resize(&self, size: PhysicalSize<u32>) {
+        if size.width > 0 && size.height > 0 {
+            self.gl_surface.resize(
+                &self.gl_context,
+                NonZeroU32::new(size.width).unwrap(),
+                NonZeroU32::new(size.height).unwrap(),
+            );
+            unsafe {
+                self.gl
+                    .Viewport(0, 0, size.width as i32, size.height as i32);
+            }
+        }
+    }
+
+    pub fn swap_buffers(&self) {
+        self.gl_surface
+            .swap_buffers(&self.gl_context)
+            .expect("Failed to swap buffers");
+    }
+}